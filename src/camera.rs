@@ -0,0 +1,75 @@
+use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::*,
+};
+
+use crate::{BoardConfig, HEIGHT, TILE_SIZE, WIDTH};
+
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 4.0;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_camera)
+            .add_system_to_stage(CoreStage::PostUpdate, pan_camera)
+            .add_system_to_stage(CoreStage::PostUpdate, zoom_camera.after(pan_camera));
+    }
+}
+
+// Spawns the main camera with an orthographic scale picked so the whole
+// board fits in the window, however big the board turns out to be
+fn spawn_camera(mut commands: Commands, config: Res<BoardConfig>) {
+    let mut camera = OrthographicCameraBundle::new_2d();
+
+    let board_width = config.width as f32 * TILE_SIZE;
+    let board_height = config.height as f32 * TILE_SIZE;
+    // A little breathing room around the edge of the board
+    let scale = (board_width / WIDTH).max(board_height / HEIGHT) * 1.1;
+
+    camera.orthographic_projection.scale = scale.max(MIN_ZOOM);
+
+    commands.spawn_bundle(camera);
+}
+
+// Drag-to-pan with the middle mouse button, or space+left-click
+fn pan_camera(
+    mouse_input: Res<Input<MouseButton>>,
+    key_input: Res<Input<KeyCode>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut query: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+) {
+    let panning = mouse_input.pressed(MouseButton::Middle)
+        || (key_input.pressed(KeyCode::Space) && mouse_input.pressed(MouseButton::Left));
+
+    if !panning {
+        return;
+    }
+
+    let delta: Vec2 = motion_events.iter().map(|ev| ev.delta).sum();
+
+    if let Ok((mut transform, projection)) = query.get_single_mut() {
+        // The projection's scale already accounts for zoom, so panning
+        // speed stays consistent with what's on screen
+        transform.translation.x -= delta.x * projection.scale;
+        transform.translation.y += delta.y * projection.scale;
+    }
+}
+
+// Scroll-wheel zoom, with the scale clamped to the fixed MIN_ZOOM..MAX_ZOOM
+// range (a startup scale above MAX_ZOOM is pulled into range on first scroll)
+fn zoom_camera(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut query: Query<&mut OrthographicProjection, With<Camera>>,
+) {
+    let scroll: f32 = wheel_events.iter().map(|ev| ev.y).sum();
+
+    if scroll == 0. {
+        return;
+    }
+
+    if let Ok(mut projection) = query.get_single_mut() {
+        projection.scale = (projection.scale * (1. - scroll * 0.1)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::util::CursorPos;
+use crate::{pos_to_tile_coords, tile_to_world_pos, BoardConfig, Coord, TILE_SIZE};
+
+pub struct HighlightPlugin;
+
+impl Plugin for HighlightPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HoveredTile::default())
+            .add_startup_system(spawn_highlight)
+            .add_system(update_highlight);
+    }
+}
+
+// The tile coordinate currently under the cursor, if any. Kept as a
+// resource so other systems (chording, tooltips, ...) can reuse it instead
+// of recomputing it from CursorPos
+#[derive(Default)]
+pub struct HoveredTile(pub Option<Coord>);
+
+// Marker for the hover highlight sprite
+#[derive(Component)]
+struct Highlight;
+
+fn spawn_highlight(mut commands: Commands) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(1., 1., 1., 0.35),
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., 1.5),
+            visibility: Visibility { is_visible: false },
+            ..default()
+        })
+        .insert(Highlight);
+}
+
+fn update_highlight(
+    cursor: Res<CursorPos>,
+    config: Res<BoardConfig>,
+    mut hovered: ResMut<HoveredTile>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<Highlight>>,
+) {
+    hovered.0 = pos_to_tile_coords(&config, (cursor.x, cursor.y));
+
+    let (mut transform, mut vis) = match query.get_single_mut() {
+        Ok(highlight) => highlight,
+        Err(_) => return,
+    };
+
+    match hovered.0 {
+        Some(coord) => {
+            let pos = tile_to_world_pos(&config, coord);
+            transform.translation.x = pos.x;
+            transform.translation.y = pos.y;
+            vis.is_visible = true;
+        }
+        None => vis.is_visible = false,
+    }
+}
@@ -1,14 +1,71 @@
+mod camera;
+mod highlight;
+mod tile_select;
 mod util;
 
+use std::collections::VecDeque;
+
 use bevy::{input::system::exit_on_esc_system, prelude::*, utils::{HashSet, HashMap}};
 use itertools::Itertools;
+// NOTE: board generation moved from `fastrand` to `rand` (StdRng) so board
+// seeds are reproducible. This checkout has no Cargo.toml to edit, but a
+// real manifest needs `rand` added and `fastrand` removed as a dependency
+// for this to resolve.
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use camera::*;
+use highlight::*;
+use tile_select::*;
 use util::*;
 
 const WIDTH: f32 = 1024.;
 const HEIGHT: f32 = 768.;
 const TILE_SIZE: f32 = 32.;
-const BOARD_DIM: (i32, i32) = (20, 15);
-const MINE_NUM: i32 = 40;
+
+// How many mines to place on the board
+#[derive(Debug, Clone, Copy)]
+enum MineCount {
+    // An exact number of mines
+    Absolute(i32),
+    // A fraction of the total tile count, e.g. 0.1 for a 10% mine density
+    Density(f32),
+}
+
+// A resource describing the board the player wants to play on: its
+// dimensions, how many mines to place, and (optionally) a seed for
+// deterministic generation
+struct BoardConfig {
+    width: i32,
+    height: i32,
+    mines: MineCount,
+    seed: Option<u64>,
+}
+
+impl BoardConfig {
+    fn dim(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    // Resolves `mines` into an absolute count, clamped to the number of tiles
+    fn mine_count(&self) -> i32 {
+        let average_mine_count = match self.mines {
+            MineCount::Absolute(n) => n,
+            MineCount::Density(density) => ((self.width * self.height) as f32 * density) as i32,
+        };
+
+        average_mine_count.min(self.width * self.height)
+    }
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        BoardConfig {
+            width: 20,
+            height: 15,
+            mines: MineCount::Absolute(40),
+            seed: None,
+        }
+    }
+}
 
 const NUM_COLOURS: [Color; 9] = [
     Color::BLACK,
@@ -27,6 +84,7 @@ enum GameState {
     // Menu
     Playing,
     GameOver,
+    Won,
 }
 
 // Marker for a tile sprite
@@ -57,103 +115,129 @@ struct RevealEvent;
 
 struct FlagEvent;
 
-fn in_bounds(&(x, y): &Coord) -> bool {
-    x >= 0 && y >= 0 && x < BOARD_DIM.0 && y < BOARD_DIM.1
+// An event triggered when the player has revealed every non-mine tile
+struct WinEvent;
+
+fn in_bounds(config: &BoardConfig, &(x, y): &Coord) -> bool {
+    x >= 0 && y >= 0 && x < config.width && y < config.height
 }
 
-// Creates a random board from the constants at the start of the file
-fn generate_board() -> BoardState {
+// Creates a random board according to `config`. Generation is seeded: the
+// same `config.seed` always produces the same board, which a `None` seed
+// forgoes in favour of a randomly seeded board every time.
+fn generate_board(config: &BoardConfig) -> BoardState {
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let (width, height) = config.dim();
     let mut mines = HashSet::new();
     // protect ourselves from adding more mines than there are tiles
-    let mut mines_left = MINE_NUM.min(BOARD_DIM.0 * BOARD_DIM.1);
+    let mut mines_left = config.mine_count();
 
     while mines_left > 0 {
-        let coord = (fastrand::i32(0..BOARD_DIM.0), fastrand::i32(0..BOARD_DIM.1));
+        let coord = (rng.gen_range(0..width), rng.gen_range(0..height));
 
         if mines.insert(coord) {
             mines_left -= 1;
         }
     }
 
-    let nums = (0..BOARD_DIM.0)
-        .cartesian_product(0..BOARD_DIM.1)
+    let nums = (0..width)
+        .cartesian_product(0..height)
         .filter(|p| !mines.contains(p))
         .map(|p| {
             let n = (-1..2)
                 .cartesian_product(-1..2)
                 .map(|(x, y)| (x + p.0, y + p.1))
-                .filter(in_bounds)
+                .filter(|p| in_bounds(config, p))
                 .map(|p| mines.contains(&p) as i32)
                 .sum();
 
             (p, n)
         }).collect();
 
-    BoardState { 
-        mines, 
-        flags: HashSet::new(), 
+    BoardState {
+        mines,
+        flags: HashSet::new(),
         revealed: HashSet::new(),
-        nums 
+        nums
     }
 }
 
-fn reveal_board(board: &mut BoardState, pos: Coord) -> bool {
-    // You can't reveal a tile that's flagged. Also,
-    // if this tile is already revealed dont worry about it and just return
-    let mut res = false;
+// Floods outwards from `pos`, revealing it and, if it has no neighbouring
+// mines, its connected region of zero-tiles. Runs as an explicit worklist
+// rather than recursing, so it stays bounded-stack on arbitrarily large
+// boards. Returns whether a mine was revealed.
+fn reveal_board(config: &BoardConfig, board: &mut BoardState, pos: Coord) -> bool {
+    let mut to_visit = VecDeque::from([pos]);
+
+    while let Some(pos) = to_visit.pop_front() {
+        // You can't reveal a tile that's flagged. Also,
+        // if this tile is already revealed dont worry about it and just continue
+        if board.flags.contains(&pos) || !board.revealed.insert(pos) {
+            continue;
+        }
 
-    if !board.flags.contains(&pos) && board.revealed.insert(pos) {
         if board.mines.contains(&pos) {
             // Game over!
             info!("Clicked a mine!!! at position {pos:?}");
             return true;
         } else if let Some(0) = board.nums.get(&pos) {
             // If there are no mines around this tile reveal all the tiles around it
-            let to_check = (-1..2)
+            let neighbours = (-1..2)
                 .cartesian_product(-1..2)
-                .filter(|&p| p != (0,0))
-                .map(|(x,y)| (x + pos.0, y + pos.1))
-                .filter(in_bounds);
+                .filter(|&p| p != (0, 0))
+                .map(|(x, y)| (x + pos.0, y + pos.1))
+                .filter(|p| in_bounds(config, p));
 
-            for neighbour in to_check {
-                res |= reveal_board(board, neighbour);
-            }
+            to_visit.extend(neighbours);
         }
     }
 
-    return res;
+    false
 }
 
 // Takes screen coordinates and converts them to either Some integer coordinates
 // representing which tile the mouse is hovering over, or None if it isn't hovering
 // over a tile.
-fn pos_to_tile_coords(pos: (f32, f32)) -> Option<Coord> {
+fn pos_to_tile_coords(config: &BoardConfig, pos: (f32, f32)) -> Option<Coord> {
     let coords = (
-        ((pos.0 - (-0.5 * (BOARD_DIM.0) as f32 * TILE_SIZE)) / TILE_SIZE) as i32,
-        ((pos.1 - (-0.5 * (BOARD_DIM.1) as f32 * TILE_SIZE)) / TILE_SIZE) as i32
+        ((pos.0 - (-0.5 * (config.width) as f32 * TILE_SIZE)) / TILE_SIZE) as i32,
+        ((pos.1 - (-0.5 * (config.height) as f32 * TILE_SIZE)) / TILE_SIZE) as i32
     );
 
-    if coords.0 >= 0 && coords.1 >= 0 && coords.0 < BOARD_DIM.0 && coords.1 < BOARD_DIM.1 {
+    if coords.0 >= 0 && coords.1 >= 0 && coords.0 < config.width && coords.1 < config.height {
         Some(coords)
     } else {
         None
     }
 }
 
-fn setup(mut commands: Commands, server: Res<AssetServer>) {
+// The inverse of pos_to_tile_coords: the world-space position of the centre
+// of a tile
+fn tile_to_world_pos(config: &BoardConfig, coord: Coord) -> Vec2 {
+    Vec2::new(
+        -0.5 * config.width as f32 * TILE_SIZE + (coord.0 as f32 + 0.5) * TILE_SIZE,
+        -0.5 * config.height as f32 * TILE_SIZE + (coord.1 as f32 + 0.5) * TILE_SIZE,
+    )
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>, config: Res<BoardConfig>) {
     commands.insert_resource(CursorPos::default());
     // Load the resources we'll need to create everything
     let font = server.load("fonts/FiraSans-Bold.ttf");
     let tile_spr = server.load("sprites/tile.png");
     let mine_spr = server.load("sprites/mine.png");
     let flag_spr = server.load("sprites/flag.png");
-    
+
     let text_align = TextAlignment {
         horizontal: HorizontalAlign::Center,
         vertical: VerticalAlign::Center,
     };
 
-    let board = generate_board();
+    let board = generate_board(&config);
 
     let spawn_tile = |c: &mut ChildBuilder, pos: Coord| {
         c.spawn_bundle(SpriteBundle {
@@ -205,19 +289,18 @@ fn setup(mut commands: Commands, server: Res<AssetServer>) {
     };
 
     // Spawn our entities
-    // The camera
-    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    // The camera is spawned by CameraPlugin so it can size itself to the board
 
     // The board w/ tiles
     commands
         .spawn_bundle(TransformBundle::from_transform(Transform::from_xyz(
-            -0.5 * (BOARD_DIM.0 - 1) as f32 * TILE_SIZE,
-            -0.5 * (BOARD_DIM.1 - 1) as f32 * TILE_SIZE,
+            -0.5 * (config.width - 1) as f32 * TILE_SIZE,
+            -0.5 * (config.height - 1) as f32 * TILE_SIZE,
             0.,
         )))
         .with_children(|parent| {
-            for y in 0..BOARD_DIM.1 {
-                for x in 0..BOARD_DIM.0 {
+            for y in 0..config.height {
+                for x in 0..config.width {
                     spawn_tile(parent, (x, y));
                 }
             }
@@ -228,23 +311,23 @@ fn setup(mut commands: Commands, server: Res<AssetServer>) {
 }
 
 fn detect_presses(
-    cursor: Res<CursorPos>, 
-    mouse_input: Res<Input<MouseButton>>,
+    mut tcr: EventReader<TileClicked>,
+    config: Res<BoardConfig>,
     mut board: ResMut<BoardState>,
     mut rew: EventWriter<RevealEvent>,
     mut few: EventWriter<FlagEvent>,
     mut state: ResMut<State<GameState>>,
 ) {
-    if let Some(pos) = pos_to_tile_coords((cursor.x, cursor.y)) {
-        if mouse_input.just_pressed(MouseButton::Left) && !board.revealed.contains(&pos) {
+    for &TileClicked { coord: pos, button } in tcr.iter() {
+        if button == MouseButton::Left && !board.revealed.contains(&pos) {
             // Reveal the board n stuff
-            if reveal_board(&mut board, pos) {
+            if reveal_board(&config, &mut board, pos) {
                 state.set(GameState::GameOver).unwrap();
             }
 
             // Also send out an event saying the board has been clicked
             rew.send(RevealEvent);
-        } else if mouse_input.just_pressed(MouseButton::Right) && !board.revealed.contains(&pos) {
+        } else if button == MouseButton::Right && !board.revealed.contains(&pos) {
             // Is there an easier way to just flip whether or not it is a flag or not?
             if !board.flags.insert(pos) {
                 board.flags.remove(&pos);
@@ -255,6 +338,38 @@ fn detect_presses(
     }
 }
 
+// Checks whether every non-mine tile has been revealed, and if so transitions
+// to GameState::Won, flags all remaining mines and fires a WinEvent
+fn detect_win(
+    config: Res<BoardConfig>,
+    mut board: ResMut<BoardState>,
+    mut few: EventWriter<FlagEvent>,
+    mut wew: EventWriter<WinEvent>,
+    mut state: ResMut<State<GameState>>,
+) {
+    let total_tiles = (config.width * config.height) as usize;
+
+    // `!board.revealed.is_empty()` guards against the degenerate case where
+    // every tile is a mine (e.g. a 100% density config): total_tiles -
+    // mines.len() is 0 there, which would otherwise match immediately with
+    // nothing revealed
+    // `is_disjoint` keeps a revealed mine (a losing click) from counting
+    // towards the total
+    if !board.revealed.is_empty()
+        && board.revealed.is_disjoint(&board.mines)
+        && board.revealed.len() == total_tiles - board.mines.len()
+    {
+        let mines: Vec<Coord> = board.mines.iter().copied().collect();
+        for mine in mines {
+            board.flags.insert(mine);
+        }
+
+        state.set(GameState::Won).unwrap();
+        few.send(FlagEvent);
+        wew.send(WinEvent);
+    }
+}
+
 fn update_tile_sprites(
     er: EventReader<RevealEvent>, 
     mut query: Query<(&BoardCoord, &mut Visibility), With<Tile>>,
@@ -281,6 +396,7 @@ fn update_flag_sprites(
 
 fn main() {
     App::new()
+        .insert_resource(BoardConfig::default())
         .insert_resource(Msaa { samples: 4 })
         .insert_resource(WindowDescriptor {
             title: "minesweeper!!!".to_string(),
@@ -291,15 +407,20 @@ fn main() {
         })
         .add_plugins(DefaultPlugins)
         .add_plugin(UtilPlugin)
+        .add_plugin(TileSelectPlugin)
+        .add_plugin(CameraPlugin)
+        .add_plugin(HighlightPlugin)
         .add_state(GameState::Playing)
         .add_event::<RevealEvent>()
         .add_event::<FlagEvent>()
+        .add_event::<WinEvent>()
         .add_startup_system(setup)
         .add_system(exit_on_esc_system)
         .add_system_set(SystemSet::on_update(GameState::Playing)
             .with_system(detect_presses)
-            .with_system(update_tile_sprites.after(detect_presses))
-            .with_system(update_flag_sprites.after(detect_presses))
+            .with_system(detect_win.after(detect_presses))
+            .with_system(update_tile_sprites.after(detect_win))
+            .with_system(update_flag_sprites.after(detect_win))
         )
         .run();
 }
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::util::CursorPos;
+use crate::{pos_to_tile_coords, BoardConfig, Coord};
+
+pub struct TileSelectPlugin;
+
+impl Plugin for TileSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TileClicked>()
+            .add_system_to_stage(CoreStage::PreUpdate, emit_tile_clicked);
+    }
+}
+
+// An event fired when the player clicks on a tile, unless the click landed
+// over a ZoneNotClickable
+pub struct TileClicked {
+    pub coord: Coord,
+    pub button: MouseButton,
+}
+
+// Marks a world-space rect where clicks should be swallowed before they can
+// reach the board, e.g. a HUD panel or menu overlaying the tiles.
+// No HUD exists in this tree yet to spawn one on, so nothing constructs this
+// component today; `emit_tile_clicked` already queries for and honours it,
+// ready for the first UI overlay that needs it.
+#[allow(dead_code)]
+#[derive(Component)]
+pub struct ZoneNotClickable {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl ZoneNotClickable {
+    fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= self.min.x && pos.x <= self.max.x && pos.y >= self.min.y && pos.y <= self.max.y
+    }
+}
+
+fn emit_tile_clicked(
+    cursor: Res<CursorPos>,
+    mouse_input: Res<Input<MouseButton>>,
+    config: Res<BoardConfig>,
+    zones: Query<&ZoneNotClickable>,
+    mut tcw: EventWriter<TileClicked>,
+) {
+    let button = [MouseButton::Left, MouseButton::Right]
+        .into_iter()
+        .find(|&button| mouse_input.just_pressed(button));
+
+    let button = match button {
+        Some(button) => button,
+        None => return,
+    };
+
+    let world_pos = Vec2::new(cursor.x, cursor.y);
+
+    if zones.iter().any(|zone| zone.contains(world_pos)) {
+        return;
+    }
+
+    if let Some(coord) = pos_to_tile_coords(&config, (cursor.x, cursor.y)) {
+        tcw.send(TileClicked { coord, button });
+    }
+}
@@ -45,6 +45,8 @@ fn update_cursor_position(
         let ndc = (screen_pos / window_size) * 2.0 - Vec2::ONE;
 
         // matrix for undoing the projection and camera transform
+        // This uses the camera's actual transform/projection, so it stays
+        // correct as CameraPlugin pans and zooms the camera around
         let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix.inverse();
 
         // use it to convert ndc to world-space coordinates